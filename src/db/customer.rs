@@ -1,4 +1,6 @@
 use crate::models::customer::{Customer, CustomerDocument, CustomerInput};
+use async_trait::async_trait;
+use base64::Engine;
 use chrono::Utc;
 use futures::stream::TryStreamExt;
 use mongodb::{
@@ -6,66 +8,489 @@ use mongodb::{
     Database,
 };
 use rocket::serde::json::Json;
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Mutex;
+
+/// A type that maps onto a MongoDB collection. Implementing this needs just
+/// the `COLLECTION` name: `to_document`/`from_document` default to BSON
+/// (de)serialization through the `Serialize`/`DeserializeOwned` supertraits,
+/// and `collection()` returns a collection handle typed as `Self`. The trait
+/// has no `find`/`insert`/`update` helpers of its own; queries and writes are
+/// run on the collection handle directly.
+pub trait Model: Serialize + DeserializeOwned {
+    const COLLECTION: &'static str;
+
+    fn to_document(&self) -> mongodb::error::Result<Document> {
+        mongodb::bson::to_document(self).map_err(mongodb::error::Error::from)
+    }
+
+    fn from_document(document: Document) -> mongodb::error::Result<Self> {
+        mongodb::bson::from_document(document).map_err(mongodb::error::Error::from)
+    }
+
+    fn collection(db: &Database) -> mongodb::Collection<Self> {
+        db.collection(Self::COLLECTION)
+    }
+}
+
+impl Model for CustomerDocument {
+    const COLLECTION: &'static str = "customer";
+}
+
+impl From<CustomerDocument> for Customer {
+    fn from(document: CustomerDocument) -> Self {
+        Customer {
+            id: document.id.to_string(),
+            name: document.name.to_string(),
+            created_at: document.created_at.to_string(),
+        }
+    }
+}
+
+/// Storage-agnostic interface for the customer operations used by the route
+/// handlers. Implemented once against a live Mongo connection
+/// ([`MongoCustomerRepository`]) and once against an in-process store
+/// ([`InMemoryCustomerRepository`]) so the rest of the application can depend
+/// on `&dyn CustomerRepository` instead of a concrete `mongodb::Database`.
+#[async_trait]
+pub trait CustomerRepository: Send + Sync {
+    async fn find(&self, limit: i64, page: i64) -> mongodb::error::Result<Vec<Customer>>;
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Customer>>;
+
+    async fn insert(&self, input: Json<CustomerInput>) -> mongodb::error::Result<String>;
+
+    async fn update(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomerInput>,
+    ) -> mongodb::error::Result<Option<Customer>>;
+
+    async fn delete(&self, oid: ObjectId) -> mongodb::error::Result<Option<Customer>>;
+}
+
+/// Production implementation, delegating to the free functions below so the
+/// existing Mongo query logic is reused rather than duplicated.
+pub struct MongoCustomerRepository {
+    db: Database,
+    options: CustomerRepoOptions,
+}
+
+impl MongoCustomerRepository {
+    pub fn new(db: Database) -> Self {
+        Self::with_options(db, CustomerRepoOptions::default())
+    }
+
+    pub fn with_options(db: Database, options: CustomerRepoOptions) -> Self {
+        Self { db, options }
+    }
+}
+
+#[async_trait]
+impl CustomerRepository for MongoCustomerRepository {
+    async fn find(&self, limit: i64, page: i64) -> mongodb::error::Result<Vec<Customer>> {
+        find_customer(&self.db, limit, page, Some(&self.options)).await
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Customer>> {
+        find_customer_by_id(&self.db, oid, Some(&self.options)).await
+    }
+
+    async fn insert(&self, input: Json<CustomerInput>) -> mongodb::error::Result<String> {
+        insert_customer(&self.db, input, Some(&self.options)).await
+    }
+
+    async fn update(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomerInput>,
+    ) -> mongodb::error::Result<Option<Customer>> {
+        update_customer_by_id(&self.db, oid, input, Some(&self.options)).await
+    }
+
+    async fn delete(&self, oid: ObjectId) -> mongodb::error::Result<Option<Customer>> {
+        delete_customer_by_id(&self.db, oid, Some(&self.options)).await
+    }
+}
+
+/// In-process stand-in for [`MongoCustomerRepository`] backed by a `Vec`
+/// behind a mutex. Lets the route handlers and their tests run without a
+/// MongoDB instance, e.g. in CI.
+#[derive(Default)]
+pub struct InMemoryCustomerRepository {
+    customers: Mutex<Vec<Customer>>,
+}
+
+impl InMemoryCustomerRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CustomerRepository for InMemoryCustomerRepository {
+    async fn find(&self, limit: i64, page: i64) -> mongodb::error::Result<Vec<Customer>> {
+        let customers = self.customers.lock().unwrap();
+        let skip = usize::try_from((page - 1) * limit).unwrap_or(0);
+        let take = usize::try_from(limit).unwrap_or(0);
+
+        Ok(customers.iter().skip(skip).take(take).cloned().collect())
+    }
+
+    async fn find_by_id(&self, oid: ObjectId) -> mongodb::error::Result<Option<Customer>> {
+        let customers = self.customers.lock().unwrap();
+        let id = oid.to_string();
+
+        Ok(customers.iter().find(|c| c.id == id).cloned())
+    }
+
+    async fn insert(&self, input: Json<CustomerInput>) -> mongodb::error::Result<String> {
+        let mut customers = self.customers.lock().unwrap();
+        let id = ObjectId::new().to_string();
+
+        customers.push(Customer {
+            id: id.clone(),
+            name: input.name.clone(),
+            created_at: Utc::now().to_string(),
+        });
+
+        Ok(id)
+    }
+
+    async fn update(
+        &self,
+        oid: ObjectId,
+        input: Json<CustomerInput>,
+    ) -> mongodb::error::Result<Option<Customer>> {
+        let mut customers = self.customers.lock().unwrap();
+        let id = oid.to_string();
+
+        let Some(customer) = customers.iter_mut().find(|c| c.id == id) else {
+            return Ok(None);
+        };
+
+        customer.name = input.name.clone();
+        customer.created_at = Utc::now().to_string();
+
+        Ok(Some(customer.clone()))
+    }
+
+    async fn delete(&self, oid: ObjectId) -> mongodb::error::Result<Option<Customer>> {
+        let mut customers = self.customers.lock().unwrap();
+        let id = oid.to_string();
+
+        let Some(index) = customers.iter().position(|c| c.id == id) else {
+            return Ok(None);
+        };
+
+        Ok(Some(customers.remove(index)))
+    }
+}
+
+/// A single operation to apply as part of a [`bulk_customers`] batch.
+pub enum CustomerOperation {
+    Insert(CustomerInput),
+    Update { oid: ObjectId, input: CustomerInput },
+    Delete { oid: ObjectId },
+}
+
+/// Outcome of a [`bulk_customers`] call, mirroring the counts MongoDB's bulk
+/// write API reports plus any per-operation write errors.
+#[derive(Debug, Default)]
+pub struct BulkWriteSummary {
+    pub inserted_count: u64,
+    pub modified_count: u64,
+    pub deleted_count: u64,
+    pub write_errors: Vec<mongodb::error::WriteError>,
+}
+
+/// Executes a batch of customer inserts/updates/deletes as a single MongoDB
+/// bulk write instead of one round trip per operation, so importing
+/// thousands of customers costs one network batch rather than N.
+pub async fn bulk_customers(
+    db: &Database,
+    operations: Vec<CustomerOperation>,
+    ordered: bool,
+) -> mongodb::error::Result<BulkWriteSummary> {
+    let collection = db.collection::<Document>(CustomerDocument::COLLECTION);
+    let namespace = collection.namespace();
+
+    let mut models = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let model = match operation {
+            CustomerOperation::Insert(input) => mongodb::options::WriteModel::InsertOne {
+                namespace: namespace.clone(),
+                document: doc! { "name": input.name, "createdAt": Utc::now() },
+            },
+            CustomerOperation::Update { oid, input } => mongodb::options::WriteModel::UpdateOne {
+                namespace: namespace.clone(),
+                filter: doc! { "_id": oid },
+                update: doc! { "$set": doc! { "name": input.name, "createdAt": Utc::now() } }
+                    .into(),
+                array_filters: None,
+                collation: None,
+                hint: None,
+                upsert: None,
+            },
+            CustomerOperation::Delete { oid } => mongodb::options::WriteModel::DeleteOne {
+                namespace: namespace.clone(),
+                filter: doc! { "_id": oid },
+                collation: None,
+                hint: None,
+            },
+        };
+        models.push(model);
+    }
+
+    // A partial failure surfaces as an `Err` carrying the per-operation write
+    // errors rather than as a field on the success value, so the error path
+    // has to be matched explicitly instead of short-circuited with `?`.
+    let (summary, write_errors) = match db.client().bulk_write(models).ordered(ordered).await {
+        Ok(result) => (result, Vec::new()),
+        Err(error) => match *error.kind {
+            mongodb::error::ErrorKind::BulkWrite(bulk_error) => (
+                bulk_error.partial_result.unwrap_or_default(),
+                bulk_error.write_errors.into_values().collect(),
+            ),
+            _ => return Err(error),
+        },
+    };
+
+    Ok(BulkWriteSummary {
+        inserted_count: summary.inserted_count,
+        modified_count: summary.modified_count,
+        deleted_count: summary.deleted_count,
+        write_errors,
+    })
+}
+
+/// Time bucket granularity for [`CustomerAggregationRequest::group_by`].
+pub enum DateBucket {
+    Day,
+    Month,
+    Year,
+}
+
+impl DateBucket {
+    fn date_format(&self) -> &'static str {
+        match self {
+            DateBucket::Day => "%Y-%m-%d",
+            DateBucket::Month => "%Y-%m",
+            DateBucket::Year => "%Y",
+        }
+    }
+}
+
+/// What to group customers by when running [`aggregate_customers`].
+pub enum CustomerGroupBy {
+    /// Bucket `createdAt` by the given granularity, e.g. customers created per day.
+    CreatedAt(DateBucket),
+    /// Group by the first `n` characters of `name`, e.g. a name-prefix histogram.
+    NamePrefix(u32),
+}
+
+/// A typed request for [`aggregate_customers`], translated into a
+/// `$match`/`$group`/`$sort` pipeline rather than pulling whole documents and
+/// counting them in Rust.
+pub struct CustomerAggregationRequest {
+    pub group_by: CustomerGroupBy,
+    pub match_filter: Option<Document>,
+}
+
+/// One row of an [`aggregate_customers`] result: the group key and how many
+/// customers fell into it.
+#[derive(serde::Deserialize)]
+pub struct CustomerAggregationRow {
+    #[serde(rename = "_id")]
+    pub key: String,
+    pub count: u64,
+}
+
+/// Runs a grouping/counting aggregation over the customer collection, e.g.
+/// customers created per day or a name-prefix histogram, using
+/// `collection.aggregate(pipeline)` instead of pulling whole documents.
+pub async fn aggregate_customers(
+    db: &Database,
+    request: CustomerAggregationRequest,
+) -> mongodb::error::Result<Vec<CustomerAggregationRow>> {
+    let collection = db.collection::<Document>(CustomerDocument::COLLECTION);
+
+    let mut pipeline = Vec::new();
+    if let Some(match_filter) = request.match_filter {
+        pipeline.push(doc! { "$match": match_filter });
+    }
+
+    let group_key = match request.group_by {
+        CustomerGroupBy::CreatedAt(bucket) => doc! {
+            "$dateToString": { "format": bucket.date_format(), "date": "$createdAt" }
+        },
+        CustomerGroupBy::NamePrefix(n) => doc! {
+            "$substrCP": ["$name", 0, n as i32]
+        },
+    };
+
+    pipeline.push(doc! { "$group": { "_id": group_key, "count": { "$sum": 1 } } });
+    pipeline.push(doc! { "$sort": { "_id": 1 } });
+
+    let mut cursor = collection.aggregate(pipeline).await?;
+
+    let mut rows = vec![];
+    while let Some(result) = cursor.try_next().await? {
+        let row: CustomerAggregationRow = mongodb::bson::from_document(result)?;
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// Per-operation read/write tuning: `find_customer` and `find_customer_by_id`
+/// apply `read_preference` to their queries, and `insert_customer`,
+/// `update_customer_by_id` and `delete_customer_by_id` apply `write_concern`
+/// to their writes, so a deployment can route reads to secondaries while
+/// writes keep a durable write concern. A `None` field leaves the driver's
+/// default in place.
+#[derive(Clone, Default)]
+pub struct CustomerRepoOptions {
+    pub read_preference: Option<mongodb::options::ReadPreference>,
+    pub write_concern: Option<mongodb::options::WriteConcern>,
+}
 
 pub async fn find_customer(
     db: &Database,
     limit: i64,
     page: i64,
+    options: Option<&CustomerRepoOptions>,
 ) -> mongodb::error::Result<Vec<Customer>> {
-    let collection = db.collection::<CustomerDocument>("customer");
+    let collection = CustomerDocument::collection(db);
 
-    let mut cursor = collection
+    let mut find = collection
         .find(doc! { "name": doc! { "$exists": true } })
         .limit(limit)
-        .skip(u64::try_from((page - 1) * limit).unwrap())
-        .await?;
+        .skip(u64::try_from((page - 1) * limit).unwrap());
+    if let Some(read_preference) = options.and_then(|o| o.read_preference.clone()) {
+        find = find.read_preference(read_preference);
+    }
+
+    let mut cursor = find.await?;
 
     let mut customers: Vec<Customer> = vec![];
     while let Some(result) = cursor.try_next().await? {
-        let _id = result.id;
-        let name = result.name;
-        let created_at = result.created_at;
-        let customer_json = Customer {
-            id: _id.to_string(),
-            name: name.to_string(),
-            created_at: created_at.to_string(),
-        };
-        customers.push(customer_json);
+        customers.push(result.into());
     }
 
     Ok(customers)
 }
 
+/// A page of customers returned by [`find_customer_keyset`], along with the
+/// opaque token to request the next page.
+pub struct CustomerPage {
+    pub items: Vec<Customer>,
+    pub next_token: Option<String>,
+}
+
+/// Encodes an `ObjectId` as the opaque `after`/`next_token` used by
+/// [`find_customer_keyset`].
+fn encode_token(oid: &ObjectId) -> String {
+    base64::engine::general_purpose::STANDARD.encode(oid.bytes())
+}
+
+/// Decodes a token produced by [`encode_token`] back into an `ObjectId`.
+fn decode_token(token: &str) -> mongodb::error::Result<ObjectId> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(token)
+        .map_err(|e| mongodb::error::Error::custom(e.to_string()))?;
+    let bytes: [u8; 12] = bytes
+        .try_into()
+        .map_err(|_| mongodb::error::Error::custom("invalid pagination token"))?;
+
+    Ok(ObjectId::from_bytes(bytes))
+}
+
+/// Cursor-based alternative to [`find_customer`]: instead of `skip`ping and
+/// discarding `(page - 1) * limit` documents (which gets slower on deep pages
+/// and can return duplicates/gaps under concurrent inserts), callers pass the
+/// `after` token from the previous page's `next_token`. The query becomes
+/// `find({ _id: { $gt: after_id } }).sort({ _id: 1 }).limit(limit + 1)`,
+/// fetching one extra row only to detect whether a next page exists before
+/// dropping it. This runs in O(limit) regardless of page depth and stays
+/// monotonic while new customers are inserted, so prefer it over the
+/// skip-based `find_customer`, which is kept only for backward compatibility.
+pub async fn find_customer_keyset(
+    db: &Database,
+    limit: i64,
+    after: Option<String>,
+) -> mongodb::error::Result<CustomerPage> {
+    let collection = CustomerDocument::collection(db);
+
+    let filter = match after {
+        Some(token) => doc! { "_id": { "$gt": decode_token(&token)? } },
+        None => doc! {},
+    };
+
+    let mut cursor = collection
+        .find(filter)
+        .sort(doc! { "_id": 1 })
+        .limit(limit + 1)
+        .await?;
+
+    let mut items = vec![];
+    while let Some(result) = cursor.try_next().await? {
+        items.push(result);
+    }
+
+    let has_more = items.len() > limit as usize;
+    if has_more {
+        items.pop();
+    }
+    let next_token = if has_more {
+        items.last().map(|last| encode_token(&last.id))
+    } else {
+        None
+    };
+
+    let items = items.into_iter().map(Customer::from).collect();
+
+    Ok(CustomerPage { items, next_token })
+}
+
 pub async fn find_customer_by_id(
     db: &Database,
     oid: ObjectId,
+    options: Option<&CustomerRepoOptions>,
 ) -> mongodb::error::Result<Option<Customer>> {
-    let collection = db.collection::<CustomerDocument>("customer");
+    let collection = CustomerDocument::collection(db);
 
-    let Some(customer_doc) = collection.find_one(doc! {"_id":oid }).await? else {
-        return Ok(None);
-    };
+    let mut find_one = collection.find_one(doc! {"_id":oid });
+    if let Some(read_preference) = options.and_then(|o| o.read_preference.clone()) {
+        find_one = find_one.read_preference(read_preference);
+    }
 
-    let customer_json = Customer {
-        id: customer_doc.id.to_string(),
-        name: customer_doc.name.to_string(),
-        created_at: customer_doc.created_at.to_string(),
+    let Some(customer_doc) = find_one.await? else {
+        return Ok(None);
     };
 
-    Ok(Some(customer_json))
+    Ok(Some(customer_doc.into()))
 }
 
 pub async fn insert_customer(
     db: &Database,
     input: Json<CustomerInput>,
+    options: Option<&CustomerRepoOptions>,
 ) -> mongodb::error::Result<String> {
-    let collection = db.collection::<Document>("customer");
+    let collection = db.collection::<Document>(CustomerDocument::COLLECTION);
 
+    // Built by hand rather than via `CustomerDocument::to_document()`: that
+    // would serialize `created_at` through chrono's `Serialize` impl as an
+    // RFC3339 string instead of the BSON `DateTime` every other write path
+    // here stores, leaving the collection with a mixed `createdAt` type.
     let created_at = Utc::now();
+    let mut insert_one =
+        collection.insert_one(doc! { "name": input.name.clone(), "createdAt": created_at });
+    if let Some(write_concern) = options.and_then(|o| o.write_concern.clone()) {
+        insert_one = insert_one.write_concern(write_concern);
+    }
 
-    let insert_one_result = collection
-        .insert_one(doc! {"name": input.name.clone(), "createdAt": created_at})
-        .await?;
+    let insert_one_result = insert_one.await?;
 
     Ok(insert_one_result.inserted_id.to_string())
 }
@@ -74,62 +499,151 @@ pub async fn update_customer_by_id(
     db: &Database,
     oid: ObjectId,
     input: Json<CustomerInput>,
+    options: Option<&CustomerRepoOptions>,
 ) -> mongodb::error::Result<Option<Customer>> {
-    let collection = db.collection::<CustomerDocument>("customer");
+    let collection = CustomerDocument::collection(db);
     let created_at: DateTime = DateTime::now();
 
-    let find = collection
+    let mut find = collection
         .find_one_and_update(
             doc! { "_id": oid },
             doc! { "$set": doc! { "name": input.name.clone(), "createdAt": created_at } },
         )
-        .return_document(mongodb::options::ReturnDocument::After)
-        .await;
+        .return_document(mongodb::options::ReturnDocument::After);
+    if let Some(write_concern) = options.and_then(|o| o.write_concern.clone()) {
+        find = find.write_concern(write_concern);
+    }
+
+    let find = find.await;
 
     let Some(customer_doc) = find? else {
         return Ok(None);
     };
 
-    let customer_json = Customer {
-        id: customer_doc.id.to_string(),
-        name: customer_doc.name.to_string(),
-        created_at: customer_doc.created_at.to_string(),
-    };
-
-    Ok(Some(customer_json))
+    Ok(Some(customer_doc.into()))
 }
 
 pub async fn delete_customer_by_id(
     db: &Database,
     oid: ObjectId,
+    options: Option<&CustomerRepoOptions>,
 ) -> mongodb::error::Result<Option<Customer>> {
-    let collection = db.collection::<CustomerDocument>("customer");
+    let collection = CustomerDocument::collection(db);
+
+    let mut find_one_and_delete = collection.find_one_and_delete(doc! {"_id":oid });
+    if let Some(write_concern) = options.and_then(|o| o.write_concern.clone()) {
+        find_one_and_delete = find_one_and_delete.write_concern(write_concern);
+    }
 
     // if you just unwrap,, when there is no document it results in 500 error.
-    let Some(customer_doc) = collection.find_one_and_delete(doc! {"_id":oid }).await? else {
+    let Some(customer_doc) = find_one_and_delete.await? else {
         return Ok(None);
     };
 
-    // transform ObjectId to String
-    let customer_json = Customer {
-        id: customer_doc.id.to_string(),
-        name: customer_doc.name.to_string(),
-        created_at: customer_doc.created_at.to_string(),
-    };
-
-    Ok(Some(customer_json))
+    Ok(Some(customer_doc.into()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[rocket::async_test]
+    async fn test_in_memory_repository_crud() {
+        let repo = InMemoryCustomerRepository::new();
+
+        let input = Json(CustomerInput {
+            name: "John Doe".to_string(),
+        });
+        let inserted_id = repo.insert(input).await.unwrap();
+        let oid = ObjectId::parse_str(&inserted_id).unwrap();
+
+        let found = repo.find_by_id(oid).await.unwrap();
+        assert_eq!(found.unwrap().name, "John Doe");
+
+        let update_input = Json(CustomerInput {
+            name: "Updated Name".to_string(),
+        });
+        let updated = repo.update(oid, update_input).await.unwrap();
+        assert_eq!(updated.unwrap().name, "Updated Name");
+
+        let page = repo.find(10, 1).await.unwrap();
+        assert_eq!(page.len(), 1);
+
+        let deleted = repo.delete(oid).await.unwrap();
+        assert!(deleted.is_some());
+        assert!(repo.find_by_id(oid).await.unwrap().is_none());
+    }
+
+    #[rocket::async_test]
+    async fn test_bulk_customers() {
+        let db = initialize_test_database().await;
+
+        let operations = vec![
+            CustomerOperation::Insert(CustomerInput {
+                name: "Bulk One".to_string(),
+            }),
+            CustomerOperation::Insert(CustomerInput {
+                name: "Bulk Two".to_string(),
+            }),
+        ];
+
+        let result = bulk_customers(&db, operations, true).await;
+
+        assert!(result.is_ok());
+
+        let summary = result.unwrap();
+        assert_eq!(summary.inserted_count, 2);
+        assert!(summary.write_errors.is_empty());
+
+        cleanup_test_database(&db).await;
+    }
+
+    #[rocket::async_test]
+    async fn test_aggregate_customers_name_prefix() {
+        let db = initialize_test_database().await;
+
+        insert_test_customers(&db).await;
+
+        let request = CustomerAggregationRequest {
+            group_by: CustomerGroupBy::NamePrefix(8),
+            match_filter: None,
+        };
+        let result = aggregate_customers(&db, request).await;
+
+        assert!(result.is_ok());
+
+        let rows = result.unwrap();
+        assert!(!rows.is_empty());
+        assert!(rows.iter().all(|row| row.count > 0));
+
+        cleanup_test_database(&db).await;
+    }
+
+    #[rocket::async_test]
+    async fn test_find_customer_keyset() {
+        let db = initialize_test_database().await;
+
+        insert_test_customers(&db).await;
+
+        let first_page = find_customer_keyset(&db, 10, None).await.unwrap();
+        assert_eq!(first_page.items.len(), 10);
+        assert!(first_page.next_token.is_some());
+
+        let second_page = find_customer_keyset(&db, 10, first_page.next_token)
+            .await
+            .unwrap();
+        assert_eq!(second_page.items.len(), 10);
+        assert!(second_page.next_token.is_none());
+
+        cleanup_test_database(&db).await;
+    }
+
     #[rocket::async_test]
     async fn test_find_customer() {
         let db = initialize_test_database().await;
 
         insert_test_customers(&db).await;
-        let result = find_customer(&db, 10, 1).await;
+        let result = find_customer(&db, 10, 1, None).await;
 
         assert!(result.is_ok());
 
@@ -145,7 +659,7 @@ mod tests {
 
         let customer_id = insert_test_customer(&db).await;
 
-        let result = find_customer_by_id(&db, customer_id).await;
+        let result = find_customer_by_id(&db, customer_id, None).await;
 
         assert!(result.is_ok());
 
@@ -163,7 +677,7 @@ mod tests {
             name: "John Doe".to_string(),
         });
 
-        let result = insert_customer(&db, input).await;
+        let result = insert_customer(&db, input, None).await;
 
         assert!(result.is_ok());
 
@@ -183,7 +697,7 @@ mod tests {
             name: "Updated Name".to_string(),
         });
 
-        let result = update_customer_by_id(&db, customer_id, input).await;
+        let result = update_customer_by_id(&db, customer_id, input, None).await;
 
         assert!(result.is_ok());
 
@@ -200,7 +714,7 @@ mod tests {
 
         let customer_id = insert_test_customer(&db).await;
 
-        let result = delete_customer_by_id(&db, customer_id).await;
+        let result = delete_customer_by_id(&db, customer_id, None).await;
 
         assert!(result.is_ok());
 
@@ -218,23 +732,17 @@ mod tests {
             .unwrap();
         let db = client.database("test_db");
 
-        db.collection::<CustomerDocument>("customer")
-            .drop()
-            .await
-            .unwrap();
+        CustomerDocument::collection(&db).drop().await.unwrap();
 
         db
     }
 
     async fn cleanup_test_database(db: &Database) {
-        db.collection::<CustomerDocument>("customer")
-            .drop()
-            .await
-            .unwrap();
+        CustomerDocument::collection(db).drop().await.unwrap();
     }
 
     async fn insert_test_customers(db: &Database) {
-        let collection = db.collection::<CustomerDocument>("customer");
+        let collection = CustomerDocument::collection(db);
 
         for i in 1..=20 {
             let customer = CustomerDocument {
@@ -248,7 +756,7 @@ mod tests {
     }
 
     async fn insert_test_customer(db: &Database) -> ObjectId {
-        let collection = db.collection::<CustomerDocument>("customer");
+        let collection = CustomerDocument::collection(db);
 
         let customer = CustomerDocument {
             id: ObjectId::new(),